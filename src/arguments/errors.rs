@@ -28,6 +28,9 @@ pub enum ParseErr {
     DelayNaN(usize),
     /// The job delay parameter was not set.
     DelayNoValue,
+    /// The `--delimiter` parameter was not given a value, or its value is
+    /// neither a single byte nor one of the `\t`/`\n`/`\0` escapes.
+    DelimNoValue,
     /// An error occurred with accessing the unprocessed file.
     File(FileErr),
     /// The joblog parameter was not set.
@@ -82,6 +85,9 @@ impl ParseErr {
             ParseErr::DelayNoValue => {
                 let _ = stderr.write(b"no delay parameter was defined.\n");
             },
+            ParseErr::DelimNoValue => {
+                let _ = stderr.write(b"no valid delimiter parameter was defined.\n");
+            },
             ParseErr::JoblogNoValue => {
                 let _ = stderr.write(b"no joblog parameter was defined.\n");
             },
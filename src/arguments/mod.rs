@@ -0,0 +1,94 @@
+mod errors;
+
+pub use self::errors::{FileErr, ParseErr};
+
+/// Flags parsed out of `argv` that affect input reading and output
+/// handling.
+#[derive(Debug)]
+pub struct Args {
+    /// The byte that separates each input record.
+    pub delimiter: u8,
+    /// Whether `--keep-order` was passed.
+    pub keep_order: bool,
+    /// Whether `--tag` was passed.
+    pub tag: bool,
+}
+
+impl Default for Args {
+    fn default() -> Args { Args { delimiter: b'\n', keep_order: false, tag: false } }
+}
+
+impl Args {
+    /// Scans `arguments` for `-0`/`--null`, `--delimiter <char>`,
+    /// `--keep-order` and `--tag`.
+    pub fn parse(arguments: &[String]) -> Result<Args, ParseErr> {
+        let mut args = Args::default();
+        let mut iter = arguments.iter();
+
+        while let Some(argument) = iter.next() {
+            match argument.as_str() {
+                "-0" | "--null" => args.delimiter = 0,
+                "--delimiter" => {
+                    let value = iter.next().ok_or(ParseErr::DelimNoValue)?;
+                    args.delimiter = parse_delimiter(value).ok_or(ParseErr::DelimNoValue)?;
+                }
+                "--keep-order" => args.keep_order = true,
+                "--tag" => args.tag = true,
+                _ => (),
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Interprets a `--delimiter` value: a single literal byte, or one of the
+/// `\t`/`\n`/`\0` escapes.
+fn parse_delimiter(value: &str) -> Option<u8> {
+    match value {
+        "\\t" => Some(b'\t'),
+        "\\n" => Some(b'\n'),
+        "\\0" => Some(0),
+        _ if value.len() == 1 => value.bytes().next(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &[&str]) -> Result<Args, ParseErr> {
+        Args::parse(&raw.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn null_flag_sets_nul_delimiter() {
+        assert_eq!(args(&["-0"]).unwrap().delimiter, 0);
+        assert_eq!(args(&["--null"]).unwrap().delimiter, 0);
+    }
+
+    #[test]
+    fn delimiter_flag_accepts_escapes_and_literals() {
+        assert_eq!(args(&["--delimiter", "\\t"]).unwrap().delimiter, b'\t');
+        assert_eq!(args(&["--delimiter", "\\0"]).unwrap().delimiter, 0);
+        assert_eq!(args(&["--delimiter", ","]).unwrap().delimiter, b',');
+    }
+
+    #[test]
+    fn delimiter_flag_without_a_value_is_an_error() {
+        assert!(matches!(args(&["--delimiter"]), Err(ParseErr::DelimNoValue)));
+    }
+
+    #[test]
+    fn keep_order_flag_is_recognized() {
+        assert!(args(&["--keep-order"]).unwrap().keep_order);
+        assert!(!args(&[]).unwrap().keep_order);
+    }
+
+    #[test]
+    fn tag_flag_is_recognized() {
+        assert!(args(&["--tag"]).unwrap().tag);
+        assert!(!args(&[]).unwrap().tag);
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::{self, Read};
+
+/// Incrementally drains a child's stderr pipe, prefixing each complete
+/// line with an optional tag. Complete lines are handed back to the
+/// caller rather than written directly, so every job's stderr funnels
+/// through `receive_messages`'s single lock.
+pub struct StderrForwarder {
+    tag:       Option<String>,
+    remainder: Vec<u8>,
+}
+
+/// The outcome of a single non-blocking drain attempt.
+pub enum Drained {
+    /// The pipe had nothing ready to read yet; try again later.
+    Pending,
+    /// The pipe was closed; nothing more will ever be read from it.
+    Closed,
+    /// Zero or more complete, tag-prefixed lines are ready to forward.
+    Lines(Vec<u8>),
+}
+
+impl StderrForwarder {
+    pub fn new(tag: Option<String>) -> StderrForwarder { StderrForwarder { tag, remainder: Vec::new() } }
+
+    /// Reads whatever is currently available from `source`, returning
+    /// `Pending` when a non-blocking `source` has nothing ready yet. A
+    /// blocking `source` blocks until it has data or is closed.
+    pub fn drain<R: Read>(&mut self, source: &mut R) -> io::Result<Drained> {
+        let mut chunk = [0u8; 8192];
+        let read = match source.read(&mut chunk) {
+            Ok(0) => return Ok(Drained::Closed),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(Drained::Pending),
+            Err(e) => return Err(e),
+        };
+
+        self.remainder.extend_from_slice(&chunk[..read]);
+        Ok(Drained::Lines(self.take_lines()))
+    }
+
+    /// Returns whatever partial line is left in the buffer once the child
+    /// has exited.
+    pub fn finish(&mut self) -> Vec<u8> {
+        if self.remainder.is_empty() {
+            return Vec::new();
+        }
+        let line = self.remainder.split_off(0);
+        self.format_line(&line)
+    }
+
+    /// Pulls every complete line currently buffered and formats it with
+    /// the tag prefix, leaving any trailing partial line in place.
+    fn take_lines(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(pos) = self.remainder.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.remainder.drain(..=pos).collect();
+            out.extend(self.format_line(&line));
+        }
+        out
+    }
+
+    fn format_line(&self, line: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(line.len() + 16);
+        if let Some(ref tag) = self.tag {
+            out.extend_from_slice(tag.as_bytes());
+            out.extend_from_slice(b": ");
+        }
+        out.extend_from_slice(line);
+        if !out.ends_with(b"\n") {
+            out.push(b'\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_lines_are_tagged_and_partial_ones_held_back() {
+        let mut forwarder = StderrForwarder::new(Some("job1".into()));
+        let mut source: &[u8] = b"first\nseco";
+        let lines = match forwarder.drain(&mut source).unwrap() {
+            Drained::Lines(lines) => lines,
+            _ => panic!("expected lines"),
+        };
+        assert_eq!(lines.as_slice(), b"job1: first\n");
+        assert_eq!(forwarder.finish().as_slice(), b"job1: seco\n");
+    }
+
+    #[test]
+    fn untagged_forwarder_passes_lines_through_unprefixed() {
+        let mut forwarder = StderrForwarder::new(None);
+        let mut source: &[u8] = b"hello\n";
+        let lines = match forwarder.drain(&mut source).unwrap() {
+            Drained::Lines(lines) => lines,
+            _ => panic!("expected lines"),
+        };
+        assert_eq!(lines.as_slice(), b"hello\n");
+    }
+
+    #[test]
+    fn empty_source_is_closed() {
+        let mut forwarder = StderrForwarder::new(None);
+        let mut source: &[u8] = b"";
+        assert!(matches!(forwarder.drain(&mut source), Ok(Drained::Closed)));
+    }
+}
@@ -0,0 +1,84 @@
+/// The run completed with every job succeeding.
+const SUCCESS: i32 = 0;
+/// A command could not be executed at all.
+const COMMAND_NOT_FOUND: i32 = 127;
+/// A job was killed by a signal, or the run was aborted.
+const SIGNALED: i32 = 255;
+/// The highest failed-job count representable in the exit code.
+const MAX_FAILED: i32 = 101;
+
+/// Tracks the aggregate outcome of every child spawned during a run.
+#[derive(Debug, Default)]
+pub struct ExitStatus {
+    failed:       usize,
+    signaled:     bool,
+    not_executed: bool,
+}
+
+impl ExitStatus {
+    pub fn new() -> ExitStatus { ExitStatus::default() }
+
+    /// Records that a job exited with the given status code.
+    pub fn exited(&mut self, code: i32) {
+        if code != 0 {
+            self.failed += 1;
+        }
+    }
+
+    /// Records that a job was terminated by a signal.
+    pub fn signaled(&mut self) { self.signaled = true; }
+
+    /// Records that a job could not be launched at all.
+    pub fn not_executed(&mut self) { self.not_executed = true; }
+
+    /// Computes the final process exit code for the whole run.
+    pub fn code(&self) -> i32 {
+        if self.signaled {
+            SIGNALED
+        } else if self.not_executed {
+            COMMAND_NOT_FOUND
+        } else if self.failed > 0 {
+            (self.failed as i32).min(MAX_FAILED)
+        } else {
+            SUCCESS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_succeeded_is_zero() {
+        let mut status = ExitStatus::new();
+        status.exited(0);
+        status.exited(0);
+        assert_eq!(status.code(), 0);
+    }
+
+    #[test]
+    fn failures_are_counted_and_clamped() {
+        let mut status = ExitStatus::new();
+        for _ in 0..150 {
+            status.exited(1);
+        }
+        assert_eq!(status.code(), MAX_FAILED);
+    }
+
+    #[test]
+    fn signaled_wins_over_failures() {
+        let mut status = ExitStatus::new();
+        status.exited(1);
+        status.signaled();
+        assert_eq!(status.code(), SIGNALED);
+    }
+
+    #[test]
+    fn not_executed_wins_over_failures() {
+        let mut status = ExitStatus::new();
+        status.exited(1);
+        status.not_executed();
+        assert_eq!(status.code(), COMMAND_NOT_FOUND);
+    }
+}
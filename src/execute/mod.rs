@@ -3,6 +3,7 @@ mod child;
 mod dry;
 mod exec_commands;
 mod exec_inputs;
+mod exit_status;
 mod job_log;
 mod receive;
 mod signals;
@@ -11,5 +12,6 @@ pub mod command;
 pub mod pipe;
 
 pub use self::{
-    dry::dry_run, exec_commands::ExecCommands, exec_inputs::ExecInputs, receive::receive_messages,
+    child::{Drained, StderrForwarder}, dry::dry_run, exec_commands::ExecCommands,
+    exec_inputs::ExecInputs, exit_status::ExitStatus, receive::{receive_messages, Message},
 };
@@ -0,0 +1,179 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    mem,
+    process::exit,
+    sync::mpsc::Receiver,
+};
+
+use super::ExitStatus;
+
+/// A single report sent back from a worker thread about one of its jobs.
+pub enum Message {
+    /// A chunk of a job's collected output, to be written as-is.
+    Output(usize, Vec<u8>),
+    /// A line of a job's stderr, already prefixed by its `StderrForwarder`.
+    Stderr(Vec<u8>),
+    /// The job exited with the given status code.
+    Exited(usize, i32),
+    /// The job was terminated by a signal.
+    Signaled(usize),
+    /// The job could not be launched at all.
+    NotExecuted(usize),
+}
+
+/// Completed jobs waiting on an earlier one beyond this count are flushed
+/// out ahead of time instead of growing the buffer without bound.
+const MAX_BUFFER_LENGTH: usize = 1000;
+
+/// Whether job output is written as soon as it arrives, or held until it
+/// can be flushed in strict input order.
+enum Mode {
+    /// Output is written in completion order, as it arrives.
+    Streaming,
+    /// Output is held per job index until every job before it has been
+    /// flushed, per `--keep-order`.
+    Buffering { next: usize, pending: BTreeMap<usize, Vec<u8>> },
+}
+
+impl Mode {
+    fn new(keep_order: bool) -> Mode {
+        if keep_order {
+            Mode::Buffering { next: 0, pending: BTreeMap::new() }
+        } else {
+            Mode::Streaming
+        }
+    }
+
+    /// Records a job's output. Does not by itself mark the job complete --
+    /// a job that writes nothing to stdout would otherwise never unblock
+    /// `next`, so completion is tracked separately via `complete`.
+    fn output(&mut self, stdout: &io::Stdout, index: usize, bytes: Vec<u8>) {
+        match *self {
+            Mode::Streaming => {
+                let _ = stdout.lock().write_all(&bytes);
+            }
+            Mode::Buffering { ref mut pending, .. } => {
+                pending.entry(index).or_insert_with(Vec::new).extend(bytes);
+            }
+        }
+    }
+
+    /// Marks a job as finished, whether or not it ever produced output,
+    /// and flushes whatever is now contiguously ready.
+    fn complete(&mut self, stdout: &io::Stdout, index: usize) {
+        if let Mode::Buffering { ref mut next, ref mut pending } = *self {
+            pending.entry(index).or_insert_with(Vec::new);
+            Self::flush_ready(stdout, next, pending);
+
+            // A slow job shouldn't stall output indefinitely.
+            if pending.len() > MAX_BUFFER_LENGTH {
+                if let Some(&lowest) = pending.keys().next() {
+                    *next = lowest;
+                    Self::flush_ready(stdout, next, pending);
+                }
+            }
+        }
+    }
+
+    /// Flushes whatever output remains once every job has finished,
+    /// regardless of whether it's contiguous with `next`.
+    fn flush_remaining(&mut self, stdout: &io::Stdout) {
+        if let Mode::Buffering { ref mut pending, .. } = *self {
+            for (_, bytes) in mem::take(pending) {
+                let _ = stdout.lock().write_all(&bytes);
+            }
+        }
+    }
+
+    fn flush_ready(stdout: &io::Stdout, next: &mut usize, pending: &mut BTreeMap<usize, Vec<u8>>) {
+        while let Some(ready) = pending.remove(next) {
+            let _ = stdout.lock().write_all(&ready);
+            *next += 1;
+        }
+    }
+}
+
+/// Receives job results as they complete, writes their output to standard
+/// output -- in input order when `keep_order` is set -- and exits the
+/// process with a code aggregated from every job's outcome.
+pub fn receive_messages(receiver: Receiver<Message>, njobs: usize, keep_order: bool) -> ! {
+    let mut status = ExitStatus::new();
+    let mut mode = Mode::new(keep_order);
+    let mut finished = 0;
+    let stdout = io::stdout();
+
+    while finished < njobs {
+        match receiver.recv() {
+            Ok(Message::Output(index, bytes)) => mode.output(&stdout, index, bytes),
+            Ok(Message::Stderr(bytes)) => {
+                let _ = io::stderr().lock().write_all(&bytes);
+            }
+            Ok(Message::Exited(index, code)) => {
+                status.exited(code);
+                mode.complete(&stdout, index);
+                finished += 1;
+            }
+            Ok(Message::Signaled(index)) => {
+                status.signaled();
+                mode.complete(&stdout, index);
+                finished += 1;
+            }
+            Ok(Message::NotExecuted(index)) => {
+                status.not_executed();
+                mode.complete(&stdout, index);
+                finished += 1;
+            }
+            Err(_) => break,
+        }
+    }
+
+    mode.flush_remaining(&stdout);
+    exit(status.code());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::thread;
+
+    /// A job that never writes to stdout must not permanently stall
+    /// `--keep-order` output for the jobs after it.
+    #[test]
+    fn silent_job_does_not_stall_keep_order() {
+        let (tx, rx) = channel();
+
+        thread::spawn(move || {
+            tx.send(Message::Output(1, b"one\n".to_vec())).unwrap();
+            tx.send(Message::Output(2, b"two\n".to_vec())).unwrap();
+            tx.send(Message::Exited(1, 0)).unwrap();
+            tx.send(Message::Exited(2, 0)).unwrap();
+            tx.send(Message::Exited(0, 0)).unwrap();
+        });
+
+        let mut status = ExitStatus::new();
+        let mut mode = Mode::new(true);
+        let mut finished = 0;
+        let stdout = io::stdout();
+
+        while finished < 3 {
+            match rx.recv().unwrap() {
+                Message::Output(index, bytes) => mode.output(&stdout, index, bytes),
+                Message::Exited(index, code) => {
+                    status.exited(code);
+                    mode.complete(&stdout, index);
+                    finished += 1;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if let Mode::Buffering { next, pending } = mode {
+            assert_eq!(next, 3);
+            assert!(pending.is_empty());
+        } else {
+            panic!("expected buffering mode");
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    fs::File,
+    io::{self, stdin, BufRead, BufReader, Stdin},
+    path::PathBuf,
+    time::Instant,
+};
+
+use super::InputIteratorErr;
+
+/// Where the `InputIterator` is reading its records from.
+enum Source {
+    File(BufReader<File>),
+    Stdin(BufReader<Stdin>),
+}
+
+/// Tracks how long it has been since the iterator started handing out
+/// inputs, so that callers may estimate a remaining time to completion.
+#[derive(Debug, Default)]
+pub struct ETA {
+    start: Option<Instant>,
+}
+
+impl ETA {
+    pub fn start(&mut self) { self.start = Some(Instant::now()); }
+
+    /// Seconds elapsed since the first input was read, if any has been yet.
+    pub fn elapsed(&self) -> f64 {
+        self.start.map_or(0f64, |start| start.elapsed().as_secs() as f64)
+    }
+}
+
+/// An iterator over the records of an input source (a file, or standard
+/// input), separated by a configurable delimiter byte -- `\n` by default,
+/// but `\0` when `--null`/`-0` is given, or an arbitrary byte via
+/// `--delimiter`.
+pub struct InputIterator {
+    source:         Source,
+    path:           PathBuf,
+    buffer:         Vec<u8>,
+    delimiter:      u8,
+    error:          Option<InputIteratorErr>,
+    pub eta:        ETA,
+    pub curr_argument: usize,
+    pub total_arguments: usize,
+}
+
+impl InputIterator {
+    pub fn new(path: &str, delimiter: u8) -> io::Result<InputIterator> {
+        let source = if path == "-" {
+            Source::Stdin(BufReader::new(stdin()))
+        } else {
+            Source::File(BufReader::new(File::open(path)?))
+        };
+
+        Ok(InputIterator {
+            source,
+            path: PathBuf::from(path),
+            buffer: Vec::with_capacity(1024),
+            delimiter,
+            error: None,
+            eta: ETA::default(),
+            curr_argument: 0,
+            total_arguments: 0,
+        })
+    }
+
+    /// Takes the I/O error that ended iteration early, if reading from the
+    /// source failed rather than simply running out of input.
+    pub fn take_error(&mut self) -> Option<InputIteratorErr> { self.error.take() }
+
+    /// Reads raw bytes until the next delimiter, returning the number of
+    /// bytes read -- `0` signals that the underlying source is exhausted.
+    fn read_record(&mut self) -> io::Result<usize> {
+        self.buffer.clear();
+        match self.source {
+            Source::File(ref mut reader) => reader.read_until(self.delimiter, &mut self.buffer),
+            Source::Stdin(ref mut reader) => reader.read_until(self.delimiter, &mut self.buffer),
+        }
+    }
+}
+
+impl Iterator for InputIterator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        match self.read_record() {
+            Ok(0) => return None,
+            Ok(_) => (),
+            Err(why) => {
+                self.error = Some(InputIteratorErr::FileRead(self.path.clone(), why));
+                return None;
+            }
+        }
+
+        if self.buffer.last() == Some(&self.delimiter) {
+            self.buffer.pop();
+        }
+
+        // A lone trailing delimiter never reaches here: `read_record`
+        // consumes through it in the same call that yields the record
+        // before it, so the next call sees a clean `Ok(0)` and returns
+        // `None` above -- no special-casing needed. An empty record from
+        // two consecutive delimiters, however, is legitimate and must be
+        // returned even when it's the last thing in the input.
+        if self.curr_argument == 0 {
+            self.eta.start();
+        }
+        self.curr_argument += 1;
+        self.total_arguments += 1;
+        Some(String::from_utf8_lossy(&self.buffer).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn iterator_over(name: &str, contents: &[u8], delimiter: u8) -> InputIterator {
+        let path = std::env::temp_dir().join(format!("parallel-input-iterator-test-{}", name));
+        fs::write(&path, contents).unwrap();
+        InputIterator::new(path.to_str().unwrap(), delimiter).unwrap()
+    }
+
+    #[test]
+    fn trailing_delimiter_is_not_a_phantom_record() {
+        let mut iter = iterator_over("trailing", b"a\nb\n", b'\n');
+        assert_eq!(iter.next(), Some("a".into()));
+        assert_eq!(iter.next(), Some("b".into()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn double_delimiter_yields_an_empty_record() {
+        let mut iter = iterator_over("double", b"a\n\nb", b'\n');
+        assert_eq!(iter.next(), Some("a".into()));
+        assert_eq!(iter.next(), Some("".into()));
+        assert_eq!(iter.next(), Some("b".into()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn null_delimiter_splits_on_nul_bytes() {
+        let mut iter = iterator_over("null", b"a\0b\0", 0);
+        assert_eq!(iter.next(), Some("a".into()));
+        assert_eq!(iter.next(), Some("b".into()));
+        assert_eq!(iter.next(), None);
+    }
+
+    /// A trailing double delimiter produces a genuine empty record, even
+    /// though it's the last thing in the input -- only a *single* trailing
+    /// delimiter is swallowed.
+    #[test]
+    fn trailing_double_delimiter_yields_a_final_empty_record() {
+        let mut iter = iterator_over("trailing-double", b"a\n\n", b'\n');
+        assert_eq!(iter.next(), Some("a".into()));
+        assert_eq!(iter.next(), Some("".into()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn trailing_double_null_yields_a_final_empty_record() {
+        let mut iter = iterator_over("trailing-double-null", b"a\0\0", 0);
+        assert_eq!(iter.next(), Some("a".into()));
+        assert_eq!(iter.next(), Some("".into()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn read_failure_is_surfaced_instead_of_treated_as_eof() {
+        // Reading from a directory's file descriptor fails on Unix.
+        let dir = std::env::temp_dir();
+        let mut iter = InputIterator::new(dir.to_str().unwrap(), b'\n').unwrap();
+        assert_eq!(iter.next(), None);
+        assert!(matches!(iter.take_error(), Some(InputIteratorErr::FileRead(_, _))));
+    }
+}
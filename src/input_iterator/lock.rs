@@ -0,0 +1,51 @@
+use std::sync::Mutex;
+
+use super::{InputIterator, InputIteratorErr};
+
+/// A mutex-guarded handle to the `InputIterator`, allowing multiple worker
+/// threads to pull the next available input without racing each other.
+pub struct InputsLock<'a> {
+    iterator: &'a Mutex<InputIterator>,
+}
+
+impl<'a> InputsLock<'a> {
+    pub fn new(iterator: &'a Mutex<InputIterator>) -> InputsLock<'a> { InputsLock { iterator } }
+
+    /// Locks the iterator just long enough to obtain the next input,
+    /// alongside the argument index it was read at. `Ok(None)` means the
+    /// input is exhausted; `Err` means reading it failed instead.
+    pub fn received(&self) -> Result<Option<(usize, String)>, InputIteratorErr> {
+        let mut iterator = self.iterator.lock().unwrap();
+        match iterator.next() {
+            Some(input) => Ok(Some((iterator.curr_argument - 1, input))),
+            None => match iterator.take_error() {
+                Some(err) => Err(err),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn read_failure_is_surfaced_through_received() {
+        let dir = std::env::temp_dir();
+        let iterator = Mutex::new(InputIterator::new(dir.to_str().unwrap(), b'\n').unwrap());
+        let lock = InputsLock::new(&iterator);
+        assert!(matches!(lock.received(), Err(InputIteratorErr::FileRead(_, _))));
+    }
+
+    #[test]
+    fn exhausted_input_is_a_clean_none() {
+        let path = std::env::temp_dir().join("parallel-inputs-lock-test-empty");
+        fs::write(&path, b"a\n").unwrap();
+        let iterator = Mutex::new(InputIterator::new(path.to_str().unwrap(), b'\n').unwrap());
+        let lock = InputsLock::new(&iterator);
+        assert_eq!(lock.received().unwrap(), Some((0, "a".to_string())));
+        assert_eq!(lock.received().unwrap(), None);
+    }
+}
@@ -0,0 +1,13 @@
+//! Raises the process's open file descriptor limit before spawning many
+//! concurrent jobs, so that `--jobs` with a large value doesn't run into
+//! opaque `FileErr::Open`/spawn failures once `RLIMIT_NOFILE` is exhausted.
+
+#[cfg(unix)]
+mod unix;
+
+#[cfg(unix)]
+pub use self::unix::raise_fd_limit;
+
+/// No file descriptor limits to raise on non-Unix platforms.
+#[cfg(not(unix))]
+pub fn raise_fd_limit(_njobs: usize) {}
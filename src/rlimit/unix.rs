@@ -0,0 +1,56 @@
+use libc::{self, rlimit, RLIMIT_NOFILE};
+
+/// Descriptors reserved for stdin/stdout/stderr and the joblog file.
+const RESERVED_FDS: u64 = 32;
+/// Descriptors a single job may hold open (its stdin/stdout/stderr pipes).
+const FDS_PER_JOB: u64 = 4;
+
+/// Raises the soft `RLIMIT_NOFILE` limit to support `njobs` concurrent
+/// children, capped at the hard limit. Failures are silently ignored.
+pub fn raise_fd_limit(njobs: usize) {
+    let wanted = njobs as u64 * FDS_PER_JOB + RESERVED_FDS;
+
+    unsafe {
+        let mut limit = rlimit { rlim_cur: 0, rlim_max: 0 };
+        if libc::getrlimit(RLIMIT_NOFILE, &mut limit) != 0 {
+            return;
+        }
+
+        let ceiling = match macos_ceiling() {
+            Some(max) => limit.rlim_max.min(max),
+            None => limit.rlim_max,
+        };
+        let target = wanted.min(ceiling);
+
+        if target > limit.rlim_cur {
+            limit.rlim_cur = target;
+            let _ = libc::setrlimit(RLIMIT_NOFILE, &limit);
+        }
+    }
+}
+
+/// macOS imposes a further per-process cap via `kern.maxfilesperproc`.
+#[cfg(target_os = "macos")]
+unsafe fn macos_ceiling() -> Option<u64> {
+    use std::{ffi::CString, mem, ptr};
+
+    let mut value: libc::c_int = 0;
+    let mut size = mem::size_of::<libc::c_int>();
+    let name = CString::new("kern.maxfilesperproc").unwrap();
+
+    if libc::sysctlbyname(
+        name.as_ptr(),
+        &mut value as *mut _ as *mut libc::c_void,
+        &mut size,
+        ptr::null_mut(),
+        0,
+    ) == 0
+    {
+        Some(value as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+unsafe fn macos_ceiling() -> Option<u64> { None }